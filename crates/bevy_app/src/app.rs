@@ -0,0 +1,71 @@
+use std::num::NonZeroU8;
+
+/// An event that indicates the [`App`](crate::App) should exit. Sending this event with an
+/// [`EventWriter`](bevy_ecs::event::EventWriter) is the canonical way to end a Bevy application;
+/// systems can read it with an [`EventReader`](bevy_ecs::event::EventReader) to detect that an
+/// exit was requested.
+///
+/// Carries an optional exit code so the process can report failure to whatever launched it.
+/// Use [`AppExit::Success`] for a clean shutdown and [`AppExit::Error`] (or [`AppExit::error`])
+/// when the app is ending because of a failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppExit {
+    /// The app exited without any error.
+    Success,
+    /// The app exited with a non-zero error code.
+    Error(NonZeroU8),
+}
+
+impl Default for AppExit {
+    fn default() -> Self {
+        AppExit::Success
+    }
+}
+
+impl AppExit {
+    /// Creates an [`AppExit::Error`] with the given non-zero error code. A `code` of `0` yields
+    /// [`AppExit::Success`].
+    pub fn error(code: u8) -> Self {
+        NonZeroU8::new(code).map_or(AppExit::Success, AppExit::Error)
+    }
+
+    /// Returns `true` if `self` is [`AppExit::Success`].
+    pub fn is_success(&self) -> bool {
+        matches!(self, AppExit::Success)
+    }
+
+    /// The process exit code to report to the OS: `0` for [`AppExit::Success`], or the carried
+    /// error code for [`AppExit::Error`].
+    pub fn code(&self) -> u8 {
+        match self {
+            AppExit::Success => 0,
+            AppExit::Error(code) => code.get(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_with_zero_code_falls_back_to_success() {
+        // `0` isn't a valid `NonZeroU8`, and an "error" with no error code would be a
+        // contradiction, so this should produce a plain success instead of panicking.
+        assert_eq!(AppExit::error(0), AppExit::Success);
+    }
+
+    #[test]
+    fn error_carries_the_given_code() {
+        let exit = AppExit::error(7);
+        assert_eq!(exit.code(), 7);
+        assert!(!exit.is_success());
+    }
+
+    #[test]
+    fn success_reports_code_zero() {
+        assert_eq!(AppExit::Success.code(), 0);
+        assert!(AppExit::Success.is_success());
+        assert_eq!(AppExit::default(), AppExit::Success);
+    }
+}
@@ -5,9 +5,10 @@ use crate::{
 };
 use bevy_ecs::{
     event::{Events, ManualEventReader},
-    schedule::BoxedScheduleLabel,
+    schedule::{BoxedScheduleLabel, ScheduleLabel},
 };
 use bevy_utils::{Duration, Instant};
+use std::num::NonZeroU32;
 
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
@@ -17,6 +18,75 @@ use std::{cell::RefCell, rc::Rc};
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::{prelude::*, JsCast};
 
+/// Label for a schedule that [`ScheduleRunnerPlugin`] runs exactly once as its runner winds down:
+/// right after [`RunMode::Once`] finishes its single run, or after an [`AppExit`] is observed for
+/// [`RunMode::Loop`]/[`RunMode::FixedLoop`]. Plugins can add systems here to flush state or
+/// release resources as part of an orderly shutdown.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, ScheduleLabel)]
+pub struct ShutdownFlow;
+
+/// Runs [`ShutdownFlow`], wrapped in the same `trace` span convention used for the main schedule.
+fn run_shutdown_schedule(app: &mut App) {
+    #[cfg(feature = "trace")]
+    let _shutdown_span = info_span!("shutdown schedule", name = ?ShutdownFlow).entered();
+    app.world.run_schedule(ShutdownFlow);
+}
+
+/// A `requestAnimationFrame` tick is synchronized to the browser's paint cycle (unlike
+/// `setTimeout`, which is clamped to >=4ms and throttled in background tabs) and automatically
+/// pauses while the tab is hidden, so [`schedule_next`] prefers it whenever the requested delay
+/// is no longer than a frame. Shared by [`RunMode::Loop`] and [`RunMode::FixedLoop`]'s wasm
+/// branches so the two don't drift apart on how they pace themselves.
+#[cfg(target_arch = "wasm32")]
+fn request_animation_frame(f: &Closure<dyn FnMut()>) {
+    web_sys::window()
+        .unwrap()
+        .request_animation_frame(f.as_ref().unchecked_ref())
+        .expect("Should register `requestAnimationFrame`.");
+}
+
+#[cfg(target_arch = "wasm32")]
+fn set_timeout(f: &Closure<dyn FnMut()>, dur: Duration) {
+    web_sys::window()
+        .unwrap()
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            f.as_ref().unchecked_ref(),
+            dur.as_millis() as i32,
+        )
+        .expect("Should register `setTimeout`.");
+}
+
+#[cfg(target_arch = "wasm32")]
+const FRAME_BUDGET: Duration = Duration::from_millis(1000 / 60);
+
+/// Schedules `f` to run again via [`request_animation_frame`] if `delay` fits within a frame, or
+/// [`set_timeout`] otherwise. `delay` of `None` means "as soon as possible".
+#[cfg(target_arch = "wasm32")]
+fn schedule_next(f: &Closure<dyn FnMut()>, delay: Option<Duration>) {
+    match delay {
+        Some(delay) if delay > FRAME_BUDGET => set_timeout(f, delay),
+        _ => request_animation_frame(f),
+    }
+}
+
+/// Given the current leftover `accumulator`, drains whole `period` steps from it, up to
+/// `max_catchup` of them, and returns how many ticks [`RunMode::FixedLoop`] should run this
+/// wake-up along with the leftover accumulator to carry forward. Pulled out of the tick closure
+/// so the catch-up cap and remainder-carry math can be unit tested without an ECS [`App`].
+fn accumulate_ticks(
+    accumulator: Duration,
+    period: Duration,
+    max_catchup: NonZeroU32,
+) -> (u32, Duration) {
+    let mut accumulator = accumulator;
+    let mut ticks = 0;
+    while accumulator >= period && ticks < max_catchup.get() {
+        accumulator -= period;
+        ticks += 1;
+    }
+    (ticks, accumulator)
+}
+
 /// Determines the method used to run an [`App`]'s [`Schedule`](bevy_ecs::schedule::Schedule).
 ///
 /// It is used in the [`ScheduleRunnerPlugin`].
@@ -28,6 +98,28 @@ pub enum RunMode {
         /// has completed before repeating. A value of [`None`] will not wait.
         wait: Option<Duration>,
     },
+    /// Indicates that the [`App`]'s schedule should run repeatedly at a fixed rate, using a
+    /// time accumulator to catch up on any ticks that were missed.
+    ///
+    /// Unlike [`RunMode::Loop`], which simply waits out the remainder of `wait` after each run,
+    /// `FixedLoop` tracks real elapsed time in an accumulator and drains it in whole `period`
+    /// steps, running the schedule once per step. This keeps the long-term average tick rate
+    /// exact even when individual sleeps oversleep, since any leftover time is carried over to
+    /// the next wake-up instead of being discarded.
+    FixedLoop {
+        /// The fixed simulation tick rate to target, e.g. `Duration::from_secs_f64(1.0 / 60.0)`.
+        period: Duration,
+        /// The maximum number of catch-up ticks to run on a single wake-up. Bounds the work
+        /// done after a long stall (e.g. the OS suspended the process) so the app cannot enter
+        /// a "spiral of death" where each tick takes longer than it just caught up for.
+        ///
+        /// Typed as [`NonZeroU32`] rather than `u32` because `0` would mean the schedule never
+        /// runs while the accumulator grows without bound: the tick would busy-spin forever with
+        /// no progress and no way to sleep. Making that state unrepresentable, rather than just
+        /// rejecting it in a convenience constructor, means directly constructing this variant
+        /// (its fields are `pub`) can't reintroduce the bug either.
+        max_catchup: NonZeroU32,
+    },
     /// Indicates that the [`App`]'s schedule should run only once.
     Once,
 }
@@ -38,6 +130,21 @@ impl Default for RunMode {
     }
 }
 
+/// Determines how the [`ScheduleRunnerPlugin`] waits out the delay between ticks in
+/// [`RunMode::Loop`] and [`RunMode::FixedLoop`].
+#[derive(Copy, Clone, Debug, Default)]
+pub enum TimingMode {
+    /// Wait using a single [`std::thread::sleep`] call. Cheap on CPU, but most OS schedulers
+    /// will not wake the thread until some time after the requested duration has elapsed, so
+    /// the effective tick rate runs a little slower than requested.
+    #[default]
+    Sleep,
+    /// Sleep for most of the delay, then busy-spin the remainder against [`Instant::now`] to
+    /// land on the target instant precisely. Gives an accurate tick rate at the cost of
+    /// spinning a CPU core for the last millisecond or so of every wait.
+    Precise,
+}
+
 /// Configures an [`App`] to run its [`Schedule`](bevy_ecs::schedule::Schedule) according to a given
 /// [`RunMode`].
 ///
@@ -57,6 +164,8 @@ pub struct ScheduleRunnerPlugin {
     ///
     /// This is initially set to [`Main`].
     pub main_schedule_label: BoxedScheduleLabel,
+    /// Determines how the delay between ticks is waited out. Defaults to [`TimingMode::Sleep`].
+    pub timing: TimingMode,
 }
 
 impl ScheduleRunnerPlugin {
@@ -65,6 +174,7 @@ impl ScheduleRunnerPlugin {
         ScheduleRunnerPlugin {
             run_mode: RunMode::Once,
             main_schedule_label: Box::new(UpdateFlow),
+            timing: TimingMode::default(),
         }
     }
 
@@ -75,6 +185,19 @@ impl ScheduleRunnerPlugin {
                 wait: Some(wait_duration),
             },
             main_schedule_label: Box::new(UpdateFlow),
+            timing: TimingMode::default(),
+        }
+    }
+
+    /// See [`RunMode::FixedLoop`].
+    pub fn run_fixed_loop(period: Duration, max_catchup: NonZeroU32) -> Self {
+        ScheduleRunnerPlugin {
+            run_mode: RunMode::FixedLoop {
+                period,
+                max_catchup,
+            },
+            main_schedule_label: Box::new(UpdateFlow),
+            timing: TimingMode::default(),
         }
     }
 }
@@ -84,6 +207,7 @@ impl Default for ScheduleRunnerPlugin {
         ScheduleRunnerPlugin {
             run_mode: RunMode::Loop { wait: None },
             main_schedule_label: Box::new(UpdateFlow),
+            timing: TimingMode::default(),
         }
     }
 }
@@ -91,10 +215,12 @@ impl Default for ScheduleRunnerPlugin {
 impl Plugin for ScheduleRunnerPlugin {
     fn build(&self, app: &mut App) {
         let run_mode = self.run_mode;
+        let timing = self.timing;
         let main_schedule_label = self.main_schedule_label.clone();
-        app.set_runner(move |mut app: App| {
+        app.set_runner(move |mut app: App| -> AppExit {
             // Prevent panic when schedules do not exist
             app.init_schedule(main_schedule_label.clone());
+            app.init_schedule(ShutdownFlow);
 
             let mut app_exit_event_reader = ManualEventReader::<AppExit>::default();
             match run_mode {
@@ -106,6 +232,8 @@ impl Plugin for ScheduleRunnerPlugin {
                         app.world.run_schedule(main_schedule_label);
                     }
                     app.update_sub_apps();
+                    run_shutdown_schedule(&mut app);
+                    AppExit::Success
                 }
                 RunMode::Loop { wait } => {
                     let mut tick = move |app: &mut App,
@@ -154,45 +282,344 @@ impl Plugin for ScheduleRunnerPlugin {
 
                     #[cfg(not(target_arch = "wasm32"))]
                     {
-                        while let Ok(delay) = tick(&mut app, wait) {
-                            if let Some(delay) = delay {
-                                std::thread::sleep(delay);
+                        let mut oversleep_margin = OversleepMargin::default();
+                        let exit = loop {
+                            match tick(&mut app, wait) {
+                                Ok(Some(delay)) => match timing {
+                                    TimingMode::Sleep => std::thread::sleep(delay),
+                                    TimingMode::Precise => {
+                                        oversleep_margin.precise_sleep(delay);
+                                    }
+                                },
+                                Ok(None) => {}
+                                Err(exit) => break exit,
                             }
-                        }
+                        };
+                        run_shutdown_schedule(&mut app);
+                        exit
                     }
 
                     #[cfg(target_arch = "wasm32")]
                     {
-                        fn set_timeout(f: &Closure<dyn FnMut()>, dur: Duration) {
-                            web_sys::window()
-                                .unwrap()
-                                .set_timeout_with_callback_and_timeout_and_arguments_0(
-                                    f.as_ref().unchecked_ref(),
-                                    dur.as_millis() as i32,
-                                )
-                                .expect("Should register `setTimeout`.");
+                        let mut rc = Rc::new(app);
+                        let f = Rc::new(RefCell::new(None));
+                        let g = f.clone();
+
+                        let c = move || {
+                            let mut app = Rc::get_mut(&mut rc).unwrap();
+                            match tick(&mut app, wait) {
+                                Ok(delay) => schedule_next(f.borrow().as_ref().unwrap(), delay),
+                                Err(_) => {
+                                    // There is no process to report a final exit status to on the
+                                    // web, so run the shutdown schedule here and let the closure
+                                    // simply stop rescheduling itself.
+                                    run_shutdown_schedule(app);
+                                }
+                            }
+                        };
+                        *g.borrow_mut() = Some(Closure::wrap(Box::new(c) as Box<dyn FnMut()>));
+                        schedule_next(g.borrow().as_ref().unwrap(), None);
+
+                        // The wasm event loop keeps ticking asynchronously after this function
+                        // returns, so there is no final `AppExit` to report synchronously.
+                        AppExit::Success
+                    }
+                }
+                RunMode::FixedLoop {
+                    period,
+                    max_catchup,
+                } => {
+                    let mut accumulator = Duration::ZERO;
+                    let mut last_time = Instant::now();
+
+                    let mut tick = move |app: &mut App| -> Result<Duration, AppExit> {
+                        let now = Instant::now();
+                        accumulator += now - last_time;
+                        last_time = now;
+
+                        if let Some(app_exit_events) =
+                            app.world.get_resource_mut::<Events<AppExit>>()
+                        {
+                            if let Some(exit) = app_exit_event_reader.iter(&app_exit_events).last()
+                            {
+                                return Err(exit.clone());
+                            }
                         }
-                        let asap = Duration::from_millis(1);
 
+                        let (ticks_to_run, remaining) =
+                            accumulate_ticks(accumulator, period, max_catchup);
+                        accumulator = remaining;
+
+                        for _ in 0..ticks_to_run {
+                            {
+                                #[cfg(feature = "trace")]
+                                let _main_schedule_span =
+                                    info_span!("main schedule", name = ?main_schedule_label)
+                                        .entered();
+                                app.world.run_schedule(&main_schedule_label);
+                            }
+                            app.update_sub_apps();
+                            app.world.clear_trackers();
+
+                            if let Some(app_exit_events) =
+                                app.world.get_resource_mut::<Events<AppExit>>()
+                            {
+                                if let Some(exit) =
+                                    app_exit_event_reader.iter(&app_exit_events).last()
+                                {
+                                    return Err(exit.clone());
+                                }
+                            }
+                        }
+
+                        // `period - accumulator` aligns the next wake-up to the schedule instead
+                        // of always waiting a full `period`, so oversleeping on one wake is paid
+                        // back by a shorter wait (or an immediate catch-up tick) on the next.
+                        Ok(period.saturating_sub(accumulator))
+                    };
+
+                    #[cfg(not(target_arch = "wasm32"))]
+                    {
+                        let mut oversleep_margin = OversleepMargin::default();
+                        let exit = loop {
+                            match tick(&mut app) {
+                                Ok(delay) if !delay.is_zero() => match timing {
+                                    TimingMode::Sleep => std::thread::sleep(delay),
+                                    TimingMode::Precise => {
+                                        oversleep_margin.precise_sleep(delay);
+                                    }
+                                },
+                                Ok(_) => {}
+                                Err(exit) => break exit,
+                            }
+                        };
+                        run_shutdown_schedule(&mut app);
+                        exit
+                    }
+
+                    #[cfg(target_arch = "wasm32")]
+                    {
                         let mut rc = Rc::new(app);
                         let f = Rc::new(RefCell::new(None));
                         let g = f.clone();
 
                         let c = move || {
                             let mut app = Rc::get_mut(&mut rc).unwrap();
-                            let delay = tick(&mut app, wait);
-                            match delay {
+                            match tick(&mut app) {
                                 Ok(delay) => {
-                                    set_timeout(f.borrow().as_ref().unwrap(), delay.unwrap_or(asap))
+                                    schedule_next(f.borrow().as_ref().unwrap(), Some(delay))
+                                }
+                                Err(_) => {
+                                    // There is no process to report a final exit status to on the
+                                    // web, so run the shutdown schedule here and let the closure
+                                    // simply stop rescheduling itself.
+                                    run_shutdown_schedule(app);
                                 }
-                                Err(_) => {}
                             }
                         };
                         *g.borrow_mut() = Some(Closure::wrap(Box::new(c) as Box<dyn FnMut()>));
-                        set_timeout(g.borrow().as_ref().unwrap(), asap);
-                    };
+                        schedule_next(g.borrow().as_ref().unwrap(), None);
+
+                        // The wasm event loop keeps ticking asynchronously after this function
+                        // returns, so there is no final `AppExit` to report synchronously.
+                        AppExit::Success
+                    }
                 }
             }
         });
     }
 }
+
+/// Tracks the observed overshoot of [`std::thread::sleep`] and uses it to drive
+/// [`TimingMode::Precise`] waits: sleep for most of the delay, then busy-spin the last sliver
+/// against [`Instant::now`] to land on the target instant exactly.
+///
+/// The margin subtracted from the sleep starts at a conservative [`INITIAL_MARGIN`] and adapts
+/// towards the platform's real oversleep behavior as samples accumulate, using an exponentially
+/// weighted mean and variance. Each new sample is also clamped to [`MAX_SAMPLE`] before folding
+/// it in. Together these bound the influence of any single outlier (the OS suspending the
+/// process, a slow page fault, a neighboring process hogging the CPU): an unweighted running
+/// mean would let one such stall permanently inflate the margin for the rest of the process's
+/// life, to the point that `margin()` could exceed every future `delay` and `precise_sleep` would
+/// busy-spin the entire wait forever instead of shrinking back down.
+#[cfg(not(target_arch = "wasm32"))]
+struct OversleepMargin {
+    initialized: bool,
+    mean_secs: f64,
+    var_secs: f64,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+const INITIAL_MARGIN: Duration = Duration::from_millis(2);
+
+/// Individual oversleep samples are clamped to this before being folded into the estimate.
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_SAMPLE: Duration = Duration::from_millis(50);
+
+/// Weight given to each new sample. A stale sample's contribution decays to roughly a third
+/// (a factor of about `e`) every `1 / EWMA_ALPHA` ticks, rather than the `1 / n` decay of an
+/// unweighted running mean.
+#[cfg(not(target_arch = "wasm32"))]
+const EWMA_ALPHA: f64 = 0.1;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for OversleepMargin {
+    fn default() -> Self {
+        Self {
+            initialized: false,
+            mean_secs: INITIAL_MARGIN.as_secs_f64(),
+            var_secs: 0.0,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl OversleepMargin {
+    /// The estimated worst-case amount by which `std::thread::sleep` will overshoot, as a
+    /// margin to subtract from the requested sleep before busy-spinning the remainder.
+    fn margin(&self) -> Duration {
+        if !self.initialized {
+            return INITIAL_MARGIN;
+        }
+        let std_dev = self.var_secs.sqrt();
+        Duration::from_secs_f64((self.mean_secs + 3.0 * std_dev).max(0.0))
+    }
+
+    fn record_oversleep(&mut self, oversleep: Duration) {
+        let x = oversleep.min(MAX_SAMPLE).as_secs_f64();
+
+        if !self.initialized {
+            self.mean_secs = x;
+            self.var_secs = 0.0;
+            self.initialized = true;
+            return;
+        }
+
+        let delta = x - self.mean_secs;
+        self.mean_secs += EWMA_ALPHA * delta;
+        // Exponentially weighted variance, decaying at the same rate as the mean so old samples
+        // don't linger in either estimate longer than the other.
+        self.var_secs = (1.0 - EWMA_ALPHA) * (self.var_secs + EWMA_ALPHA * delta * delta);
+    }
+
+    /// Sleeps for approximately `delay`, landing on the target instant precisely by busy-spinning
+    /// past the point where a plain `std::thread::sleep(delay)` would typically already have
+    /// overshot.
+    fn precise_sleep(&mut self, delay: Duration) {
+        let before = Instant::now();
+        let sleep_for = delay.saturating_sub(self.margin());
+
+        if !sleep_for.is_zero() {
+            std::thread::sleep(sleep_for);
+            let overslept = Instant::now().saturating_duration_since(before);
+            self.record_oversleep(overslept.saturating_sub(sleep_for));
+        }
+
+        let target = before + delay;
+        while Instant::now() < target {
+            std::hint::spin_loop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catchup(n: u32) -> NonZeroU32 {
+        NonZeroU32::new(n).unwrap()
+    }
+
+    #[test]
+    fn accumulate_ticks_runs_one_tick_per_whole_period() {
+        let period = Duration::from_millis(10);
+        let (ticks, remaining) = accumulate_ticks(Duration::from_millis(25), period, catchup(10));
+        assert_eq!(ticks, 2);
+        assert_eq!(remaining, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn accumulate_ticks_caps_at_max_catchup_and_keeps_the_rest_for_next_time() {
+        let period = Duration::from_millis(10);
+        // 95ms owed, but only 3 catch-up ticks are allowed this wake-up.
+        let (ticks, remaining) = accumulate_ticks(Duration::from_millis(95), period, catchup(3));
+        assert_eq!(ticks, 3);
+        assert_eq!(remaining, Duration::from_millis(65));
+    }
+
+    #[test]
+    fn accumulate_ticks_with_no_debt_runs_nothing() {
+        let period = Duration::from_millis(10);
+        let (ticks, remaining) = accumulate_ticks(Duration::from_millis(4), period, catchup(10));
+        assert_eq!(ticks, 0);
+        assert_eq!(remaining, Duration::from_millis(4));
+    }
+
+    #[test]
+    fn accumulate_ticks_with_max_catchup_of_one_never_wedges() {
+        // `max_catchup` can no longer be `0` (it's a `NonZeroU32`), so the smallest possible
+        // value is `1`. Even then, debt beyond one period's worth must still be carried forward
+        // rather than dropped or stalled on.
+        let period = Duration::from_millis(10);
+        let (ticks, remaining) = accumulate_ticks(Duration::from_millis(35), period, catchup(1));
+        assert_eq!(ticks, 1);
+        assert_eq!(remaining, Duration::from_millis(25));
+
+        // Running it again on the carried-over remainder keeps making progress instead of wedging:
+        // the accumulator keeps draining one period at a time rather than growing unbounded.
+        let (ticks, remaining) = accumulate_ticks(remaining, period, catchup(1));
+        assert_eq!(ticks, 1);
+        assert_eq!(remaining, Duration::from_millis(15));
+    }
+
+    #[test]
+    fn run_fixed_loop_rejects_zero_max_catchup_at_compile_time() {
+        // `NonZeroU32::new(0)` is `None`, so a caller simply cannot construct a `max_catchup` of
+        // `0` to pass to `run_fixed_loop` (or to `RunMode::FixedLoop` directly) in the first
+        // place — the invariant is enforced by the type, not by an assert a direct-construction
+        // caller could bypass.
+        assert!(NonZeroU32::new(0).is_none());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn oversleep_margin_starts_conservative() {
+        let margin = OversleepMargin::default();
+        assert_eq!(margin.margin(), INITIAL_MARGIN);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn oversleep_margin_converges_towards_repeated_samples() {
+        let mut margin = OversleepMargin::default();
+        for _ in 0..200 {
+            margin.record_oversleep(Duration::from_micros(500));
+        }
+        // After many identical samples the mean should sit close to the sampled value, and the
+        // estimated variance should have collapsed towards zero.
+        let estimate = margin.margin().as_secs_f64();
+        let sample = Duration::from_micros(500).as_secs_f64();
+        assert!(
+            (estimate - sample).abs() < 0.0002,
+            "expected margin to converge near {sample}s, got {estimate}s"
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn oversleep_margin_recovers_from_a_single_outlier() {
+        let mut margin = OversleepMargin::default();
+        // One pathological stall (e.g. the process was suspended) shouldn't permanently wreck
+        // the estimate: a clamp bounds its immediate impact, and EWMA decay lets later, normal
+        // samples pull the estimate back down instead of an unweighted mean dragging at `1/n`.
+        margin.record_oversleep(Duration::from_secs(5));
+        for _ in 0..100 {
+            margin.record_oversleep(Duration::from_micros(500));
+        }
+        assert!(
+            margin.margin() < Duration::from_millis(5),
+            "a single outlier should not permanently inflate the margin, got {:?}",
+            margin.margin()
+        );
+    }
+}